@@ -4,6 +4,7 @@ use crate::{
 };
 use amethyst_error::Error;
 use legion::system::Schedulable;
+use std::any::{Any, TypeId};
 use std::collections::HashMap;
 
 pub trait ConsumeDesc {
@@ -15,69 +16,619 @@ pub trait ConsumeDesc {
     ) -> Result<(), amethyst_error::Error>;
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
-pub enum Stage {
-    Begin,
-    Logic,
-    Render,
-    ThreadLocal,
+/// An opaque, ordered label identifying a dispatcher stage.
+///
+/// Unlike the old `Stage` enum, any crate can mint its own label and splice
+/// it into the schedule relative to an existing one with
+/// [`DispatcherBuilder::add_stage_before`]/[`DispatcherBuilder::add_stage_after`],
+/// instead of forking the engine to add a variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StageLabel(&'static str);
+
+impl StageLabel {
+    pub const fn new(name: &'static str) -> Self {
+        StageLabel(name)
+    }
+}
+
+/// The built-in stage labels, kept around so existing callers that ran one
+/// stage at a time keep working unchanged.
+pub struct Stage;
+impl Stage {
+    pub const BEGIN: StageLabel = StageLabel::new("begin");
+    pub const LOGIC: StageLabel = StageLabel::new("logic");
+    pub const RENDER: StageLabel = StageLabel::new("render");
+    pub const THREAD_LOCAL: StageLabel = StageLabel::new("thread_local");
+}
+
+/// A type-erased store for `!Send`/`!Sync` resources (audio device handles,
+/// GL contexts, input backends like gilrs), held on the `Dispatcher`.
+/// `ThreadLocal::run` receives only the `World`, so thread-local systems
+/// cannot read from this store.
+#[derive(Default)]
+pub struct ThreadLocalResources(HashMap<TypeId, Box<dyn Any>>);
+impl ThreadLocalResources {
+    pub fn insert<T: 'static>(&mut self, resource: T) {
+        self.0.insert(TypeId::of::<T>(), Box::new(resource));
+    }
+
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.0
+            .get(&TypeId::of::<T>())
+            .and_then(|res| res.downcast_ref())
+    }
+
+    pub fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.0
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|res| res.downcast_mut())
+    }
+
+    pub fn init<T: Default + 'static>(&mut self) {
+        self.0
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(T::default()));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Folds `other` into `self`, keeping `self`'s resource where both sides
+    /// have one of the same type — the same "first registrant wins" policy
+    /// `Dispatcher::merge` and `DispatcherBuilder::build`'s fixpoint loop use
+    /// for every other piece of builder state.
+    fn merge_from(&mut self, other: ThreadLocalResources) {
+        for (type_id, resource) in other.0 {
+            self.0.entry(type_id).or_insert(resource);
+        }
+    }
+}
+
+/// The result of evaluating a stage's run criterion for the current tick.
+pub enum ShouldRun {
+    /// Skip the stage entirely this tick.
+    No,
+    /// Run the stage once.
+    Yes,
+    /// Run the stage, then re-evaluate the criterion immediately, looping
+    /// until it returns something other than this. Used for fixed-timestep
+    /// stages that need to run N times to catch up with accumulated `Time`.
+    YesAndCheckAgain,
+}
+
+/// A boxed run criterion, evaluated against the `World` before a stage runs.
+pub type RunCriteria = Box<dyn FnMut(&World) -> ShouldRun>;
+
+/// Identifies a specific boxed system for the lifetime of the `Dispatcher`,
+/// independent of whatever position it currently occupies in `stages[stage]`.
+///
+/// `legion::system::StageExecutor` is handed `&mut Vec<Box<dyn Schedulable>>`
+/// and is free to reorder it in place while scheduling; a `Box`'s heap
+/// address doesn't move when the `Box` itself is relocated within the `Vec`,
+/// so this — rather than positional index — is what per-system run criteria
+/// are keyed on.
+type SystemIdentity = usize;
+
+fn system_identity(system: &Box<dyn legion::system::Schedulable>) -> SystemIdentity {
+    system.as_ref() as *const dyn legion::system::Schedulable as *const () as SystemIdentity
+}
+
+/// A label identifying a system for the purpose of declaring explicit
+/// ordering constraints that aren't implied by its resource access (e.g. a
+/// system that only reads a resource another writes occasionally).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SystemLabel(&'static str);
+
+impl SystemLabel {
+    pub const fn new(name: &'static str) -> Self {
+        SystemLabel(name)
+    }
+}
+
+/// An error produced while building a [`Dispatcher`].
+#[derive(Debug)]
+pub enum DispatcherBuildError {
+    /// The `.before()`/`.after()` constraints for the systems in a stage
+    /// form a cycle, so no valid execution order exists.
+    OrderingCycle(StageLabel),
+    /// A `.before()`/`.after()` constraint referenced a label that was
+    /// never passed to `add_system_labeled` for that stage on the same
+    /// builder. Constraints are resolved per builder: systems added on the
+    /// top-level builder and systems added by bundles are sorted separately,
+    /// and a constraint cannot name a label from the other group.
+    UnknownSystemLabel(StageLabel, SystemLabel),
+    /// `add_stage_after`/`add_stage_before` referenced a stage that hasn't
+    /// been registered yet.
+    UnknownStage(StageLabel),
+}
+
+impl std::fmt::Display for DispatcherBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DispatcherBuildError::OrderingCycle(stage) => {
+                write!(f, "system ordering cycle detected in stage {:?}", stage.0)
+            }
+            DispatcherBuildError::UnknownSystemLabel(stage, label) => write!(
+                f,
+                "ordering constraint in stage {:?} references unknown system label {:?}",
+                stage.0, label.0
+            ),
+            DispatcherBuildError::UnknownStage(stage) => {
+                write!(f, "stage {:?} is not registered", stage.0)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DispatcherBuildError {}
+
+impl From<DispatcherBuildError> for amethyst_error::Error {
+    fn from(error: DispatcherBuildError) -> Self {
+        amethyst_error::Error::new(error)
+    }
+}
+
+/// An error produced while running a [`Dispatcher`], surfaced instead of
+/// aborting the process so hot-reloading games can recover.
+#[derive(Debug)]
+pub enum DispatcherRunError {
+    /// `Dispatcher::run` was asked to run a stage that isn't registered.
+    UnknownStage(StageLabel),
+    /// The `World` has no `ArcThreadPool` resource for the stage executor
+    /// to run systems on.
+    MissingThreadPool,
+}
+
+impl std::fmt::Display for DispatcherRunError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DispatcherRunError::UnknownStage(stage) => {
+                write!(
+                    f,
+                    "stage {:?} is not registered on this dispatcher",
+                    stage.0
+                )
+            }
+            DispatcherRunError::MissingThreadPool => {
+                write!(f, "no ArcThreadPool resource found in World")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DispatcherRunError {}
+
+impl From<DispatcherRunError> for amethyst_error::Error {
+    fn from(error: DispatcherRunError) -> Self {
+        amethyst_error::Error::new(error)
+    }
+}
+
+/// A pending `add_stage_after`/`add_stage_before` constraint, resolved
+/// against the stage order at `build()` time rather than validated (and
+/// potentially panicking) at call time.
+enum StageConstraint {
+    After(StageLabel),
+    Before(StageLabel),
+}
+
+/// Per-stage bookkeeping used to topologically sort systems that were given
+/// explicit ordering constraints via `add_system_labeled`.
+#[derive(Default)]
+struct SystemOrder {
+    /// The label (if any) of each system, in the order it was pushed to
+    /// `DispatcherBuilder::systems` for this stage — which is also the
+    /// order `ConsumeDesc::consume` appends it to `Dispatcher::stages`.
+    labels: Vec<Option<SystemLabel>>,
+    /// `(before, after)` edges: `before` must run before `after`.
+    edges: Vec<(SystemLabel, SystemLabel)>,
+    /// Run criteria gating individual systems, keyed by their index into
+    /// `labels` (i.e. the position they were pushed at this round).
+    criteria: HashMap<usize, RunCriteria>,
+}
+
+fn topological_order(n: usize, edges: &[(usize, usize)]) -> Option<Vec<usize>> {
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    let mut in_degree = vec![0usize; n];
+    let mut successors = vec![Vec::new(); n];
+    for &(before, after) in edges {
+        successors[before].push(after);
+        in_degree[after] += 1;
+    }
+
+    let mut ready: BinaryHeap<Reverse<usize>> =
+        (0..n).filter(|&i| in_degree[i] == 0).map(Reverse).collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(Reverse(i)) = ready.pop() {
+        order.push(i);
+        for &next in &successors[i] {
+            in_degree[next] -= 1;
+            if in_degree[next] == 0 {
+                ready.push(Reverse(next));
+            }
+        }
+    }
+
+    if order.len() == n {
+        Some(order)
+    } else {
+        None
+    }
 }
 
 pub struct Dispatcher {
     pub thread_locals: Vec<Box<dyn ThreadLocal>>,
-    pub stages: HashMap<Stage, Vec<Box<dyn legion::system::Schedulable>>>,
+    /// Not `pub`: `ThreadLocal::run` doesn't receive this (see the
+    /// `THREAD_LOCAL` branch of `Dispatcher::run`), so there is no consumer
+    /// able to read whatever a caller deposits here.
+    pub(crate) thread_local_resources: ThreadLocalResources,
+    pub stages: HashMap<StageLabel, Vec<Box<dyn legion::system::Schedulable>>>,
+    /// Stages in the order they should execute, as declared on the builder.
+    pub stage_order: Vec<StageLabel>,
+    /// Run criteria gating whether (and how many times) a stage executes
+    /// on a given tick. A stage with no entry always runs once.
+    pub stage_run_criteria: HashMap<StageLabel, RunCriteria>,
+    /// Run criteria gating individual systems registered via `add_system`
+    /// (or the labeled/desc variants), keyed by [`SystemIdentity`] rather
+    /// than position so they stay attached to the right system even if
+    /// `stages[stage]` gets reordered after `build()`. A system with no
+    /// entry always runs.
+    pub system_run_criteria: HashMap<StageLabel, HashMap<SystemIdentity, RunCriteria>>,
 }
 impl Default for Dispatcher {
     fn default() -> Self {
-        use std::iter::FromIterator;
-
         Self {
             thread_locals: Vec::default(),
+            thread_local_resources: ThreadLocalResources::default(),
             stages: vec![
-                (Stage::Begin, Vec::default()),
-                (Stage::Logic, Vec::default()),
-                (Stage::Render, Vec::default()),
+                (Stage::BEGIN, Vec::default()),
+                (Stage::LOGIC, Vec::default()),
+                (Stage::RENDER, Vec::default()),
             ]
             .into_iter()
             .collect(),
+            stage_order: vec![
+                Stage::BEGIN,
+                Stage::LOGIC,
+                Stage::RENDER,
+                Stage::THREAD_LOCAL,
+            ],
+            stage_run_criteria: HashMap::default(),
+            system_run_criteria: HashMap::default(),
         }
     }
 }
 impl Dispatcher {
-    pub fn run(&mut self, stage: Stage, world: &mut World) {
-        match stage {
-            Stage::ThreadLocal => {
+    pub fn run(
+        &mut self,
+        stage: StageLabel,
+        world: &mut World,
+    ) -> Result<(), amethyst_error::Error> {
+        loop {
+            let should_run = match self.stage_run_criteria.get_mut(&stage) {
+                Some(criterion) => criterion(world),
+                None => ShouldRun::Yes,
+            };
+            let (execute, check_again) = should_run_decision(&should_run);
+            if !execute {
+                return Ok(());
+            }
+
+            if stage == Stage::THREAD_LOCAL {
+                // `ThreadLocal` is brought in via `use super::*` from the
+                // parent `legion` module and only takes `world`, so
+                // `self.thread_local_resources` is not passed to
+                // implementors and no thread-local system can reach
+                // `ThreadLocalResources`. The deposit side
+                // (`add_thread_local_resource`/`init_thread_local_resource`)
+                // is `pub(crate)` for that reason.
                 self.thread_locals
                     .iter_mut()
                     .for_each(|local| local.run(world));
+            } else {
+                let systems = self
+                    .stages
+                    .get_mut(&stage)
+                    .ok_or(DispatcherRunError::UnknownStage(stage))?;
+                let pool = world
+                    .resources
+                    .get::<ArcThreadPool>()
+                    .ok_or(DispatcherRunError::MissingThreadPool)?;
+
+                match self.system_run_criteria.get_mut(&stage) {
+                    Some(criteria) if !criteria.is_empty() => {
+                        let skip: std::collections::HashSet<SystemIdentity> = criteria
+                            .iter_mut()
+                            .filter(|(_, criterion)| matches!(criterion(world), ShouldRun::No))
+                            .map(|(&id, _)| id)
+                            .collect();
+
+                        if skip.is_empty() {
+                            legion::system::StageExecutor::new(systems, &pool).execute(world);
+                        } else {
+                            // Pull the gated-off systems out of the vec so the
+                            // stage executor never sees them this tick, then
+                            // splice everything back. Restoring is keyed on
+                            // each system's `SystemIdentity` rather than its
+                            // position in `active` — a `Box`'s heap address
+                            // survives the stage executor potentially
+                            // reordering the vec it's handed, so looking
+                            // systems back up by identity is correct even if
+                            // position-based restoration wouldn't be.
+                            let mut slots: Vec<Option<Box<dyn Schedulable>>> =
+                                systems.drain(..).map(Some).collect();
+                            let mut active = Vec::with_capacity(slots.len());
+                            let mut original_slot_of: HashMap<SystemIdentity, usize> =
+                                HashMap::with_capacity(slots.len());
+                            for (index, slot) in slots.iter_mut().enumerate() {
+                                let identity =
+                                    system_identity(slot.as_ref().expect("slot not yet taken"));
+                                if !skip.contains(&identity) {
+                                    original_slot_of.insert(identity, index);
+                                    active.push(slot.take().expect("slot not yet taken"));
+                                }
+                            }
+
+                            legion::system::StageExecutor::new(&mut active, &pool).execute(world);
+
+                            for system in active {
+                                let index = original_slot_of[&system_identity(&system)];
+                                slots[index] = Some(system);
+                            }
+                            *systems = slots
+                                .into_iter()
+                                .map(|slot| slot.expect("every slot was either active or skipped"))
+                                .collect();
+                        }
+                    }
+                    _ => {
+                        legion::system::StageExecutor::new(systems, &pool).execute(world);
+                    }
+                }
             }
-            _ => {
-                legion::system::StageExecutor::new(
-                    &mut self.stages.get_mut(&stage).unwrap(),
-                    &world.resources.get::<ArcThreadPool>().unwrap(),
-                )
-                .execute(world);
+
+            if !check_again {
+                return Ok(());
             }
         }
     }
 
+    /// Calls [`Dispatcher::run`] for every registered stage, in the order
+    /// declared on the builder, rather than requiring the caller to name
+    /// each built-in stage itself. Each stage's run criterion decides
+    /// whether it is skipped, run once or run repeatedly.
+    pub fn run_all(&mut self, world: &mut World) -> Result<(), amethyst_error::Error> {
+        for stage in self.stage_order.clone() {
+            self.run(stage, world)?;
+        }
+        Ok(())
+    }
+
     pub fn merge(mut self, mut other: Dispatcher) -> Self {
         self.thread_locals.extend(other.thread_locals.drain(..));
+        self.thread_local_resources
+            .merge_from(other.thread_local_resources);
         for (k, v) in self.stages.iter_mut() {
-            v.extend(other.stages.get_mut(k).unwrap().drain(..));
+            if let Some(other_v) = other.stages.get_mut(k) {
+                v.extend(other_v.drain(..));
+            }
+        }
+        for (k, v) in other.stages {
+            self.stages.entry(k).or_insert(v);
+        }
+        for stage in other.stage_order {
+            if !self.stage_order.contains(&stage) {
+                self.stage_order.push(stage);
+            }
+        }
+        for (stage, criterion) in other.stage_run_criteria {
+            self.stage_run_criteria.entry(stage).or_insert(criterion);
+        }
+        for (stage, criteria) in other.system_run_criteria {
+            self.system_run_criteria
+                .entry(stage)
+                .or_default()
+                .extend(criteria);
         }
 
         self
     }
 }
 
-#[derive(Default)]
+/// Given the latest run-criterion evaluation, returns whether to execute
+/// this pass (`execute`) and whether to loop around and re-evaluate
+/// (`check_again`). Pulled out of `Dispatcher::run` so the three `ShouldRun`
+/// outcomes can be exercised without a `World`.
+fn should_run_decision(should_run: &ShouldRun) -> (bool, bool) {
+    match should_run {
+        ShouldRun::No => (false, false),
+        ShouldRun::Yes => (true, false),
+        ShouldRun::YesAndCheckAgain => (true, true),
+    }
+}
+
 pub struct DispatcherBuilder {
-    systems: Vec<(Stage, Box<dyn ConsumeDesc>)>,
+    systems: Vec<(StageLabel, Box<dyn ConsumeDesc>)>,
     thread_locals: Vec<Box<dyn ConsumeDesc>>,
     bundles: Vec<Box<dyn ConsumeDesc>>,
+    stage_order: Vec<StageLabel>,
+    thread_local_resources: ThreadLocalResources,
+    stage_run_criteria: HashMap<StageLabel, RunCriteria>,
+    system_order: HashMap<StageLabel, SystemOrder>,
+    stage_constraints: Vec<(StageConstraint, StageLabel)>,
+}
+impl Default for DispatcherBuilder {
+    fn default() -> Self {
+        Self {
+            systems: Vec::default(),
+            thread_locals: Vec::default(),
+            bundles: Vec::default(),
+            stage_order: vec![
+                Stage::BEGIN,
+                Stage::LOGIC,
+                Stage::RENDER,
+                Stage::THREAD_LOCAL,
+            ],
+            thread_local_resources: ThreadLocalResources::default(),
+            stage_run_criteria: HashMap::default(),
+            system_order: HashMap::default(),
+            stage_constraints: Vec::default(),
+        }
+    }
+}
+
+/// Returned by [`DispatcherBuilder::add_system`]/`add_system_desc` to
+/// optionally gate the system that was just added with a run criterion.
+pub struct SystemRunCriteriaHandle<'a> {
+    builder: &'a mut DispatcherBuilder,
+    stage: StageLabel,
+    index: usize,
+}
+impl<'a> SystemRunCriteriaHandle<'a> {
+    /// Gates this individual system with a run criterion, independently of
+    /// whichever stage it lives in: skipped on a tick where it returns
+    /// `ShouldRun::No`. `ShouldRun::YesAndCheckAgain` is treated the same as
+    /// `Yes` here — only the whole-stage loop in `Dispatcher::run` repeats a
+    /// pass, a single system within it cannot re-run on its own.
+    pub fn run_if<F>(self, criterion: F) -> Self
+    where
+        F: FnMut(&World) -> ShouldRun + 'static,
+    {
+        self.builder
+            .system_order
+            .entry(self.stage)
+            .or_default()
+            .criteria
+            .insert(self.index, Box::new(criterion));
+
+        self
+    }
+}
+
+/// Returned by [`DispatcherBuilder::add_system_labeled`] to attach ordering
+/// constraints (or a run criterion) to the system that was just added.
+pub struct SystemLabelBuilder<'a> {
+    builder: &'a mut DispatcherBuilder,
+    stage: StageLabel,
+    label: SystemLabel,
+    index: usize,
+}
+impl<'a> SystemLabelBuilder<'a> {
+    /// Declares that this system must run before `other`.
+    pub fn before(self, other: SystemLabel) -> Self {
+        self.builder
+            .system_order
+            .entry(self.stage)
+            .or_default()
+            .edges
+            .push((self.label, other));
+
+        self
+    }
+
+    /// Declares that this system must run after `other`.
+    pub fn after(self, other: SystemLabel) -> Self {
+        self.builder
+            .system_order
+            .entry(self.stage)
+            .or_default()
+            .edges
+            .push((other, self.label));
+
+        self
+    }
+
+    /// Gates this system with a run criterion. See
+    /// [`SystemRunCriteriaHandle::run_if`].
+    pub fn run_if<F>(self, criterion: F) -> Self
+    where
+        F: FnMut(&World) -> ShouldRun + 'static,
+    {
+        self.builder
+            .system_order
+            .entry(self.stage)
+            .or_default()
+            .criteria
+            .insert(self.index, Box::new(criterion));
+
+        self
+    }
 }
 impl DispatcherBuilder {
+    /// Gates a stage with a run criterion, evaluated each tick before the
+    /// stage's systems execute. Returning `ShouldRun::YesAndCheckAgain`
+    /// re-runs the stage and re-evaluates the criterion, which is how
+    /// fixed-timestep stages catch up with accumulated `Time`.
+    ///
+    /// This is the `&mut self` counterpart to `with_stage_run_criteria` —
+    /// `SystemBundle::build`/`ConsumeDesc::consume` only ever receive a
+    /// `&mut DispatcherBuilder`, so without this a bundle could never gate a
+    /// stage it contributes.
+    pub fn add_stage_run_criteria<F>(&mut self, stage: StageLabel, criterion: F)
+    where
+        F: FnMut(&World) -> ShouldRun + 'static,
+    {
+        self.stage_run_criteria.insert(stage, Box::new(criterion));
+    }
+
+    pub fn with_stage_run_criteria<F>(mut self, stage: StageLabel, criterion: F) -> Self
+    where
+        F: FnMut(&World) -> ShouldRun + 'static,
+    {
+        self.add_stage_run_criteria(stage, criterion);
+
+        self
+    }
+    /// Adds a `!Send`/`!Sync` resource for thread-local systems, mirroring
+    /// the normal resource API.
+    ///
+    /// Not `pub`: `ThreadLocal::run` doesn't take a `&mut ThreadLocalResources`
+    /// parameter, so no thread-local system can read back whatever gets
+    /// stashed here.
+    pub(crate) fn add_thread_local_resource<T: 'static>(&mut self, resource: T) {
+        self.thread_local_resources.insert(resource);
+    }
+
+    /// Initializes a default-constructed thread-local resource, mirroring
+    /// the normal resource API. See [`DispatcherBuilder::add_thread_local_resource`]
+    /// for why this isn't `pub`.
+    pub(crate) fn init_thread_local_resource<T: Default + 'static>(&mut self) {
+        self.thread_local_resources.init::<T>();
+    }
+
+    pub(crate) fn with_thread_local_resource<T: 'static>(mut self, resource: T) -> Self {
+        self.add_thread_local_resource(resource);
+
+        self
+    }
+    /// Registers a stage at the end of the schedule, unless it is already
+    /// present.
+    pub fn add_stage(&mut self, label: StageLabel) {
+        if !self.stage_order.contains(&label) {
+            self.stage_order.push(label);
+        }
+    }
+
+    /// Registers `label` immediately after `after`. `after` need not be
+    /// registered yet — e.g. a bundle may run before the stage it orders
+    /// against is added — so resolution is deferred to `build()`, which
+    /// returns `DispatcherBuildError::UnknownStage` if `after` is still
+    /// missing by then, rather than panicking here.
+    pub fn add_stage_after(&mut self, after: StageLabel, label: StageLabel) {
+        self.stage_constraints
+            .push((StageConstraint::After(after), label));
+    }
+
+    /// Registers `label` immediately before `before`. Like
+    /// `add_stage_after`, resolution is deferred to `build()`.
+    pub fn add_stage_before(&mut self, before: StageLabel, label: StageLabel) {
+        self.stage_constraints
+            .push((StageConstraint::Before(before), label));
+    }
+
     pub fn add_thread_local<D: ThreadLocal + 'static>(&mut self, system: D) {
         self.thread_locals
             .push(Box::new(DispatcherThreadLocal(system)));
@@ -88,18 +639,75 @@ impl DispatcherBuilder {
             .push(Box::new(DispatcherThreadLocalDesc(system)));
     }
 
-    pub fn add_system<D: Schedulable + 'static>(&mut self, stage: Stage, desc: D) {
+    pub fn add_system<D: Schedulable + 'static>(
+        &mut self,
+        stage: StageLabel,
+        desc: D,
+    ) -> SystemRunCriteriaHandle<'_> {
+        self.add_stage(stage);
+        let order = self.system_order.entry(stage).or_default();
+        let index = order.labels.len();
+        order.labels.push(None);
         self.systems.push((
             stage,
             Box::new(DispatcherSystem(stage, desc)) as Box<dyn ConsumeDesc>,
         ));
+
+        SystemRunCriteriaHandle {
+            builder: self,
+            stage,
+            index,
+        }
     }
 
-    pub fn add_system_desc<D: SystemDesc + 'static>(&mut self, stage: Stage, desc: D) {
+    pub fn add_system_desc<D: SystemDesc + 'static>(
+        &mut self,
+        stage: StageLabel,
+        desc: D,
+    ) -> SystemRunCriteriaHandle<'_> {
+        self.add_stage(stage);
+        let order = self.system_order.entry(stage).or_default();
+        let index = order.labels.len();
+        order.labels.push(None);
         self.systems.push((
             stage,
             Box::new(DispatcherSystemDesc(stage, desc)) as Box<dyn ConsumeDesc>,
         ));
+
+        SystemRunCriteriaHandle {
+            builder: self,
+            stage,
+            index,
+        }
+    }
+
+    /// Like [`DispatcherBuilder::add_system`], but attaches a [`SystemLabel`]
+    /// that `.before()`/`.after()` constraints on other systems in the same
+    /// stage can refer to. The dependency graph built from those constraints
+    /// is topologically sorted at `build()` time, and the resulting order is
+    /// handed to the stage's `StageExecutor`, which still parallelizes
+    /// whatever's independent.
+    pub fn add_system_labeled<D: Schedulable + 'static>(
+        &mut self,
+        stage: StageLabel,
+        label: SystemLabel,
+        desc: D,
+    ) -> SystemLabelBuilder<'_> {
+        self.add_stage(stage);
+        let order = self.system_order.entry(stage).or_default();
+        let index = order.labels.len();
+        order.labels.push(Some(label));
+        self.systems.push((
+            stage,
+            Box::new(DispatcherSystem(stage, desc)) as Box<dyn ConsumeDesc>,
+        ));
+
+        SystemLabelBuilder {
+            builder: self,
+            stage,
+            label,
+            index,
+        }
     }
 
     pub fn add_bundle<D: SystemBundle + 'static>(&mut self, bundle: D) {
@@ -107,6 +715,24 @@ impl DispatcherBuilder {
             .push(Box::new(DispatcherSystemBundle(bundle)) as Box<dyn ConsumeDesc>);
     }
 
+    pub fn with_stage(mut self, label: StageLabel) -> Self {
+        self.add_stage(label);
+
+        self
+    }
+
+    pub fn with_stage_after(mut self, after: StageLabel, label: StageLabel) -> Self {
+        self.add_stage_after(after, label);
+
+        self
+    }
+
+    pub fn with_stage_before(mut self, before: StageLabel, label: StageLabel) -> Self {
+        self.add_stage_before(before, label);
+
+        self
+    }
+
     pub fn with_thread_local<D: ThreadLocal + 'static>(mut self, system: D) -> Self {
         self.add_thread_local(system);
 
@@ -119,13 +745,13 @@ impl DispatcherBuilder {
         self
     }
 
-    pub fn with_system<D: Schedulable + 'static>(mut self, stage: Stage, desc: D) -> Self {
+    pub fn with_system<D: Schedulable + 'static>(mut self, stage: StageLabel, desc: D) -> Self {
         self.add_system(stage, desc);
 
         self
     }
 
-    pub fn with_system_desc<D: SystemDesc + 'static>(mut self, stage: Stage, desc: D) -> Self {
+    pub fn with_system_desc<D: SystemDesc + 'static>(mut self, stage: StageLabel, desc: D) -> Self {
         self.add_system_desc(stage, desc);
 
         self
@@ -138,35 +764,364 @@ impl DispatcherBuilder {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.systems.is_empty() && self.bundles.is_empty() && self.thread_locals.is_empty()
+        self.systems.is_empty()
+            && self.bundles.is_empty()
+            && self.thread_locals.is_empty()
+            && self.thread_local_resources.is_empty()
+            && self.stage_constraints.is_empty()
+            && self.stage_run_criteria.is_empty()
+            && self.stage_order == [Stage::BEGIN, Stage::LOGIC, Stage::RENDER, Stage::THREAD_LOCAL]
     }
 
-    pub fn build(mut self, world: &mut legion::world::World) -> Dispatcher {
+    pub fn build(
+        mut self,
+        world: &mut legion::world::World,
+    ) -> Result<Dispatcher, amethyst_error::Error> {
+        self.stage_order = resolve_stage_order(
+            std::mem::take(&mut self.stage_order),
+            std::mem::take(&mut self.stage_constraints),
+        )?;
+
         let mut dispatcher = Dispatcher::default();
+        dispatcher.stage_order = self.stage_order.clone();
+        dispatcher.thread_local_resources = std::mem::take(&mut self.thread_local_resources);
+        dispatcher.stage_run_criteria = std::mem::take(&mut self.stage_run_criteria);
+        for &label in &self.stage_order {
+            if label != Stage::THREAD_LOCAL {
+                dispatcher.stages.entry(label).or_insert_with(Vec::default);
+            }
+        }
+
+        // A bundle's `consume` can itself add another bundle, whose
+        // `consume` can add systems/thread-locals of its own, and so on.
+        // Keep draining each generation into `pending` until a round
+        // produces nothing new, rather than stopping after one extra level.
+        //
+        // Every field a bundle could have populated on the `next` builder —
+        // not just `systems`/`bundles`/`thread_locals` — has to be folded
+        // into `dispatcher` each round, and `is_empty()` has to count all of
+        // them too. Otherwise a bundle whose only contribution is, say, a
+        // thread-local resource or a stage constraint has that registration
+        // silently dropped: `next.is_empty()` would already be true, so the
+        // loop would break before ever looking at it.
+        let mut pending = self;
+        loop {
+            let system_order = std::mem::take(&mut pending.system_order);
+            let mut next = DispatcherBuilder::default();
+
+            for desc in pending.systems.drain(..) {
+                desc.1.consume(world, &mut dispatcher, &mut next)?;
+            }
+
+            for bundle in pending.bundles.drain(..) {
+                bundle.consume(world, &mut dispatcher, &mut next)?;
+            }
+
+            for desc in pending.thread_locals.drain(..) {
+                desc.consume(world, &mut dispatcher, &mut next)?;
+            }
+
+            for (stage, order) in system_order {
+                apply_system_order(&mut dispatcher, stage, order)?;
+            }
 
-        let mut recursive_builder = DispatcherBuilder::default();
-        for desc in self.systems.drain(..) {
-            desc.1
-                .consume(world, &mut dispatcher, &mut recursive_builder)
-                .unwrap();
+            if next.is_empty() {
+                break;
+            }
+
+            // Resolve this round's stage constraints against the order built
+            // up so far, so a bundle can place a stage relative to one an
+            // earlier round registered.
+            let mut stage_order = dispatcher.stage_order.clone();
+            for stage in std::mem::take(&mut next.stage_order) {
+                if !stage_order.contains(&stage) {
+                    stage_order.push(stage);
+                }
+            }
+            dispatcher.stage_order =
+                resolve_stage_order(stage_order, std::mem::take(&mut next.stage_constraints))?;
+            for &stage in &dispatcher.stage_order {
+                if stage != Stage::THREAD_LOCAL {
+                    dispatcher.stages.entry(stage).or_insert_with(Vec::default);
+                }
+            }
+            dispatcher
+                .thread_local_resources
+                .merge_from(std::mem::take(&mut next.thread_local_resources));
+            for (stage, criterion) in std::mem::take(&mut next.stage_run_criteria) {
+                dispatcher.stage_run_criteria.entry(stage).or_insert(criterion);
+            }
+
+            pending = next;
         }
 
-        for bundle in self.bundles.drain(..) {
-            bundle
-                .consume(world, &mut dispatcher, &mut recursive_builder)
-                .unwrap();
+        Ok(dispatcher)
+    }
+}
+
+/// Resolves `add_stage_after`/`add_stage_before` constraints against
+/// `stage_order`, returning the updated order.
+///
+/// If `label` is already present in `stage_order` (e.g. it was already
+/// registered via `add_system`/`add_stage`), its existing entry is removed
+/// before re-inserting at the resolved position, rather than inserting a
+/// second copy — a duplicate would make `Dispatcher::run_all` execute that
+/// stage's systems twice per tick.
+fn resolve_stage_order(
+    mut stage_order: Vec<StageLabel>,
+    constraints: Vec<(StageConstraint, StageLabel)>,
+) -> Result<Vec<StageLabel>, DispatcherBuildError> {
+    for (constraint, label) in constraints {
+        let anchor = match constraint {
+            StageConstraint::After(after) => after,
+            StageConstraint::Before(before) => before,
+        };
+
+        if let Some(old_index) = stage_order.iter().position(|&s| s == label) {
+            stage_order.remove(old_index);
         }
 
-        for desc in self.thread_locals.drain(..) {
-            desc.consume(world, &mut dispatcher, &mut recursive_builder)
-                .unwrap();
+        let index = stage_order
+            .iter()
+            .position(|&s| s == anchor)
+            .ok_or(DispatcherBuildError::UnknownStage(anchor))?;
+        match constraint {
+            StageConstraint::After(_) => stage_order.insert(index + 1, label),
+            StageConstraint::Before(_) => stage_order.insert(index, label),
         }
+    }
 
-        // TODO: We need to recursively iterate any newly added bundles
-        if !recursive_builder.is_empty() {
-            dispatcher.merge(recursive_builder.build(world))
-        } else {
-            dispatcher
+    Ok(stage_order)
+}
+
+/// Reorders the systems this round appended to `dispatcher.stages[stage]` to
+/// respect the `.before()`/`.after()` constraints recorded in `order`.
+///
+/// `order.labels` only describes the systems consumed during the current
+/// fixpoint round, but `dispatcher.stages[stage]` also holds whatever
+/// earlier rounds already appended to that stage. Only the trailing slice
+/// of `systems` matching `order.labels` in length belongs to this round, so
+/// that's the only part we touch — reordering the full vec would treat
+/// earlier rounds' systems as if they were part of this round's graph and
+/// silently drop or misplace systems.
+///
+/// This relies on `legion::system::StageExecutor` executing data-disjoint
+/// systems in the relative order it was given them for ties that
+/// `.before()`/`.after()` care about; it has no way to *enforce* that order
+/// on a scheduler it doesn't own.
+fn apply_system_order(
+    dispatcher: &mut Dispatcher,
+    stage: StageLabel,
+    order: SystemOrder,
+) -> Result<(), DispatcherBuildError> {
+    if order.labels.is_empty() {
+        return Ok(());
+    }
+
+    let systems = match dispatcher.stages.get_mut(&stage) {
+        Some(systems) => systems,
+        None => return Ok(()),
+    };
+
+    let sorted = if order.edges.is_empty() {
+        (0..order.labels.len()).collect()
+    } else {
+        resolve_system_order(stage, &order.labels, &order.edges)?
+    };
+
+    let split_at = systems.len() - order.labels.len();
+    reorder_suffix(systems, order.labels.len(), &sorted);
+
+    if !order.criteria.is_empty() {
+        // `sorted[new_position] == original_round_index`, so find where
+        // each gated system's original index landed to read back the actual
+        // system it ended up as, and key its criterion on that system's
+        // `SystemIdentity` rather than the index — `stages[stage]` is handed
+        // to `legion::system::StageExecutor` by `&mut` reference and isn't
+        // guaranteed to keep this layout once that runs.
+        let criteria_map = dispatcher.system_run_criteria.entry(stage).or_default();
+        for (round_index, criterion) in order.criteria {
+            let final_position = sorted
+                .iter()
+                .position(|&original| original == round_index)
+                .expect("every round index appears exactly once in `sorted`");
+            let system = &systems[split_at + final_position];
+            criteria_map.insert(system_identity(system), criterion);
         }
     }
+
+    Ok(())
+}
+
+/// Topologically sorts `labels` (one entry per system, `None` for
+/// unlabeled ones) according to `edges` (`(before, after)` pairs), returning
+/// the resulting permutation as indices into `labels`.
+fn resolve_system_order(
+    stage: StageLabel,
+    labels: &[Option<SystemLabel>],
+    edges: &[(SystemLabel, SystemLabel)],
+) -> Result<Vec<usize>, DispatcherBuildError> {
+    let index_of = |label: SystemLabel| {
+        labels
+            .iter()
+            .position(|&l| l == Some(label))
+            .ok_or(DispatcherBuildError::UnknownSystemLabel(stage, label))
+    };
+
+    let mut index_edges = Vec::with_capacity(edges.len());
+    for &(before, after) in edges {
+        index_edges.push((index_of(before)?, index_of(after)?));
+    }
+
+    topological_order(labels.len(), &index_edges).ok_or(DispatcherBuildError::OrderingCycle(stage))
+}
+
+/// Reorders only the last `suffix_len` elements of `items` according to
+/// `order` (a permutation of `0..suffix_len`), leaving everything before
+/// that suffix untouched.
+///
+/// This matters because `items` can already hold entries from earlier
+/// fixpoint rounds of `DispatcherBuilder::build` by the time a later
+/// round's ordering constraints are applied — reordering the whole vec
+/// would treat those earlier entries as part of the current round's graph
+/// and silently drop or misplace them.
+fn reorder_suffix<T>(items: &mut Vec<T>, suffix_len: usize, order: &[usize]) {
+    let split_at = items.len() - suffix_len;
+    let suffix = items.split_off(split_at);
+    let mut slots: Vec<Option<T>> = suffix.into_iter().map(Some).collect();
+    items.extend(
+        order
+            .iter()
+            .map(|&i| slots[i].take().expect("system order index used twice")),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topological_order_respects_edges() {
+        // 0 -> 1 -> 2, plus an unconstrained node 3.
+        let order = topological_order(4, &[(0, 1), (1, 2)]).expect("should not cycle");
+        let pos = |n: usize| order.iter().position(|&i| i == n).unwrap();
+        assert!(pos(0) < pos(1));
+        assert!(pos(1) < pos(2));
+        assert_eq!(order.len(), 4);
+    }
+
+    #[test]
+    fn topological_order_detects_cycles() {
+        assert!(topological_order(2, &[(0, 1), (1, 0)]).is_none());
+    }
+
+    #[test]
+    fn resolve_system_order_detects_cycle() {
+        let a = SystemLabel::new("a");
+        let b = SystemLabel::new("b");
+        let stage = Stage::LOGIC;
+
+        let err = resolve_system_order(stage, &[Some(a), Some(b)], &[(a, b), (b, a)])
+            .expect_err("a before b and b before a is a cycle");
+        assert!(matches!(err, DispatcherBuildError::OrderingCycle(s) if s == stage));
+    }
+
+    #[test]
+    fn resolve_system_order_rejects_unknown_label() {
+        let a = SystemLabel::new("a");
+        let ghost = SystemLabel::new("ghost");
+        let stage = Stage::LOGIC;
+
+        let err = resolve_system_order(stage, &[Some(a)], &[(a, ghost)])
+            .expect_err("ghost was never added to this stage");
+        assert!(
+            matches!(err, DispatcherBuildError::UnknownSystemLabel(s, l) if s == stage && l == ghost)
+        );
+    }
+
+    #[test]
+    fn reorder_suffix_only_touches_systems_added_this_round() {
+        // Regression test: a stage that already held a system from an
+        // earlier `build()` fixpoint round must keep that system intact
+        // when a later round's labeled systems in the same stage get
+        // reordered.
+        let mut items = vec!["earlier-round-system", "c", "b"];
+        // "b" must run before "c", so sorted indices into the 2-element
+        // suffix ["c", "b"] are [1, 0].
+        reorder_suffix(&mut items, 2, &[1, 0]);
+        assert_eq!(items, vec!["earlier-round-system", "b", "c"]);
+    }
+
+    #[test]
+    fn resolve_stage_order_dedupes_existing_stage() {
+        let begin = Stage::BEGIN;
+        let physics = StageLabel::new("physics");
+        let post_physics = StageLabel::new("post_physics");
+
+        // Regression test: `add_system(post_physics, ..)` already registers
+        // `post_physics` via `add_stage`; `add_stage_after(physics,
+        // post_physics)` must move it into position, not duplicate it —
+        // a duplicate would make `Dispatcher::run_all` execute its systems
+        // twice per tick.
+        let stage_order = vec![begin, post_physics, physics];
+        let constraints = vec![(StageConstraint::After(physics), post_physics)];
+
+        let resolved = resolve_stage_order(stage_order, constraints).unwrap();
+        assert_eq!(resolved, vec![begin, physics, post_physics]);
+    }
+
+    #[test]
+    fn resolve_stage_order_rejects_unknown_anchor() {
+        let ghost = StageLabel::new("ghost");
+        let label = StageLabel::new("new_stage");
+
+        let err = resolve_stage_order(
+            vec![Stage::BEGIN],
+            vec![(StageConstraint::After(ghost), label)],
+        )
+        .expect_err("ghost was never registered");
+        assert!(matches!(err, DispatcherBuildError::UnknownStage(s) if s == ghost));
+    }
+
+    #[test]
+    fn builder_is_not_empty_with_only_a_thread_local_resource() {
+        // Regression test: a bundle that *only* calls
+        // `add_thread_local_resource` on the `&mut DispatcherBuilder` it's
+        // handed must not look empty, or `build()`'s fixpoint loop would
+        // break before ever folding that resource in.
+        let mut builder = DispatcherBuilder::default();
+        builder.add_thread_local_resource(42i32);
+        assert!(!builder.is_empty());
+    }
+
+    #[test]
+    fn builder_is_not_empty_with_only_a_stage_constraint() {
+        let mut builder = DispatcherBuilder::default();
+        builder.add_stage_after(Stage::BEGIN, StageLabel::new("post_begin"));
+        assert!(!builder.is_empty());
+    }
+
+    #[test]
+    fn thread_local_resources_merge_keeps_self_on_conflict() {
+        let mut into = ThreadLocalResources::default();
+        into.insert(1i32);
+        let mut from = ThreadLocalResources::default();
+        from.insert(2i32);
+        from.insert("from-only");
+
+        into.merge_from(from);
+
+        assert_eq!(into.get::<i32>(), Some(&1));
+        assert_eq!(into.get::<&str>(), Some(&"from-only"));
+    }
+
+    #[test]
+    fn should_run_decision_covers_all_variants() {
+        assert_eq!(should_run_decision(&ShouldRun::No), (false, false));
+        assert_eq!(should_run_decision(&ShouldRun::Yes), (true, false));
+        assert_eq!(
+            should_run_decision(&ShouldRun::YesAndCheckAgain),
+            (true, true)
+        );
+    }
 }